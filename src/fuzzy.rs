@@ -0,0 +1,131 @@
+// Scored subsequence matcher for the file filter box, in the spirit of the
+// `StringMatchCandidate` fuzzy scoring used by editor file pickers: every
+// query character must appear in order in the candidate, consecutive
+// matches and matches right after a path separator or word boundary score
+// higher, and gaps between matched characters are penalized.
+
+const SCORE_MATCH: i64 = 16;
+const SCORE_CONSECUTIVE_BONUS: i64 = 24;
+const SCORE_BOUNDARY_BONUS: i64 = 20;
+const PENALTY_PER_GAP_CHAR: i64 = 2;
+
+// Matches below this score are considered too weak to show.
+pub const MATCH_THRESHOLD: i64 = 0;
+
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    // Byte-index-free character positions into `candidate`, for highlighting.
+    pub positions: Vec<usize>,
+}
+
+// Lowercases a single `char` to a single `char`. Plain `char::to_lowercase`
+// can expand to more than one char (e.g. Turkish `İ` U+0130), which would
+// break the 1:1 indexing `fuzzy_match` relies on between a candidate and its
+// lowercased form; taking just the first result keeps that alignment.
+fn to_lower_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+// Returns `None` if `query` isn't a subsequence of `candidate`, or if the
+// resulting score doesn't clear `MATCH_THRESHOLD`.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let query_chars: Vec<char> = query.chars().map(to_lower_char).collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let candidate_lower: Vec<char> = candidate_chars.iter().copied().map(to_lower_char).collect();
+
+    let mut positions = Vec::with_capacity(query_chars.len());
+    let mut score: i64 = 0;
+    let mut query_idx = 0;
+    let mut last_match_idx: Option<usize> = None;
+
+    for (i, &c) in candidate_lower.iter().enumerate() {
+        if query_idx >= query_chars.len() {
+            break;
+        }
+
+        if c != query_chars[query_idx] {
+            continue;
+        }
+
+        let mut char_score = SCORE_MATCH;
+
+        match last_match_idx {
+            Some(last) if i - last - 1 == 0 => char_score += SCORE_CONSECUTIVE_BONUS,
+            Some(last) => char_score -= (i - last - 1) as i64 * PENALTY_PER_GAP_CHAR,
+            None => {}
+        }
+
+        let is_boundary = i == 0 || matches!(candidate_chars[i - 1], '/' | '\\' | '_' | '-' | '.' | ' ');
+        if is_boundary {
+            char_score += SCORE_BOUNDARY_BONUS;
+        }
+
+        score += char_score;
+        positions.push(i);
+        last_match_idx = Some(i);
+        query_idx += 1;
+    }
+
+    if query_idx < query_chars.len() || score < MATCH_THRESHOLD {
+        return None;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_in_order_subsequence() {
+        let m = fuzzy_match("abc", "aXbXc").expect("should match");
+        assert_eq!(m.positions, vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn rejects_out_of_order_or_missing_chars() {
+        assert!(fuzzy_match("abc", "acb").is_none());
+        assert!(fuzzy_match("xyz", "abc").is_none());
+    }
+
+    #[test]
+    fn rejects_empty_query() {
+        assert!(fuzzy_match("", "anything").is_none());
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        let m = fuzzy_match("ABC", "abc").expect("should match");
+        assert_eq!(m.positions, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn consecutive_match_scores_higher_than_gapped() {
+        let consecutive = fuzzy_match("ab", "ab...").unwrap();
+        let gapped = fuzzy_match("ab", "a....b").unwrap();
+        assert!(consecutive.score > gapped.score);
+    }
+
+    #[test]
+    fn boundary_match_scores_higher_than_mid_word() {
+        let boundary = fuzzy_match("f", "src/foo.rs").unwrap();
+        let mid_word = fuzzy_match("f", "buffer.rs").unwrap();
+        assert!(boundary.score > mid_word.score);
+    }
+
+    // Regression test: candidates containing codepoints that expand under
+    // `char::to_lowercase` (e.g. Turkish `İ` U+0130, which lowercases to the
+    // two-char sequence `i̇`) used to desync `candidate_chars` from
+    // `candidate_lower`'s indices and panic. See `to_lower_char`.
+    #[test]
+    fn does_not_panic_on_length_expanding_lowercase() {
+        let result = fuzzy_match("x", "İİx");
+        assert!(result.is_some());
+    }
+}
@@ -1,23 +1,53 @@
-use iced::{Element, Result, Settings, Size, Theme, Application, Command};
-use iced::widget::{button, column, container, row, scrollable, text, checkbox};
-use std::path::PathBuf;
+use iced::{Element, Result, Settings, Size, Subscription, Theme, Application, Command};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, checkbox};
+use futures::SinkExt;
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+mod fuzzy;
 mod prompt_builder;
-use prompt_builder::PromptBuilder;
+mod prompt_store;
+use fuzzy::{fuzzy_match, FuzzyMatch};
+use prompt_builder::{ExcludedItems, PromptBuilder};
+use prompt_store::{PromptStore, SavedPrompt};
+
+// How long to keep buffering filesystem events after the first one of a
+// burst before reporting them; events for the same path within that window
+// collapse into one refresh.
+const FS_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Clone)]
 pub enum Message {
     AddFolderPressed,
     FolderSelected(Option<PathBuf>),
+    FolderLoaded(PathBuf, Vec<FileSystemItem>),
     ToggleItemSelected(PathBuf, bool),
+    ToggleExpand(PathBuf),
+    ChildrenLoaded(PathBuf, Vec<FileSystemItem>),
+    DirectoryAdded { files: Vec<(PathBuf, String)>, skipped: usize },
     BuildPrompt,
     PromptBuilt(String),
     TogglePromptPanel,
+    ToggleLibraryPanel,
+    PromptTitleChanged(String),
+    SavePrompt(String),
+    LoadPrompt(String),
+    FilterChanged(String),
+    SelectAllMatches,
+    FsEvent(PathBuf, notify::EventKind),
+    ExcludePatternsChanged(String),
+    ApplyExcludePatterns,
+    ExportPromptPressed,
+    ExportPathSelected(Option<PathBuf>),
+    PromptExported(std::result::Result<PathBuf, String>),
 }
 
 #[derive(Debug, Clone)]
 pub struct Folder {
     path: PathBuf,
     items: Vec<FileSystemItem>,
+    loading: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -26,28 +56,258 @@ pub struct FileSystemItem {
     name: String,
     is_file: bool,
     selected: bool,
+    depth: usize,
+    expanded: bool,
+    children_loaded: bool,
+    loading: bool,
 }
 
 impl FileSystemItem {
-    pub fn new(path: PathBuf) -> Self {
+    pub fn new(path: PathBuf, depth: usize) -> Self {
         let name = path.file_name()
             .map(|name| name.to_string_lossy().to_string())
             .unwrap_or_else(|| "/".to_string());
         let is_file = path.is_file();
-        
+
         Self {
             path,
             name,
             is_file,
             selected: false,
+            depth,
+            expanded: false,
+            children_loaded: false,
+            loading: false,
+        }
+    }
+}
+
+// Blocking directory reads used by the async loading tasks below; kept free
+// of `self` so they can run on a `spawn_blocking` task off the UI thread.
+fn scan_top_level(path: &Path) -> Vec<FileSystemItem> {
+    let mut items: Vec<FileSystemItem> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| FileSystemItem::new(entry.path(), 0))
+                .collect()
+        })
+        .unwrap_or_default();
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+fn scan_children(path: &Path, depth: usize) -> Vec<FileSystemItem> {
+    let mut children: Vec<FileSystemItem> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| FileSystemItem::new(entry.path(), depth + 1))
+                .collect()
+        })
+        .unwrap_or_default();
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+    children
+}
+
+async fn load_folder_contents(path: PathBuf) -> Message {
+    let scan_path = path.clone();
+    let items = tokio::task::spawn_blocking(move || scan_top_level(&scan_path))
+        .await
+        .unwrap_or_default();
+
+    Message::FolderLoaded(path, items)
+}
+
+async fn load_children(path: PathBuf, depth: usize) -> Message {
+    let scan_path = path.clone();
+    let children = tokio::task::spawn_blocking(move || scan_children(&scan_path, depth))
+        .await
+        .unwrap_or_default();
+
+    Message::ChildrenLoaded(path, children)
+}
+
+async fn scan_directory_task(dir_path: PathBuf, excludes: ExcludedItems) -> Message {
+    let result = tokio::task::spawn_blocking(move || PromptBuilder::scan_directory(&dir_path, &excludes)).await;
+
+    match result {
+        Ok(Ok((files, skipped))) => Message::DirectoryAdded { files, skipped },
+        Ok(Err(e)) => {
+            println!("Error scanning directory: {}", e);
+            Message::DirectoryAdded { files: Vec::new(), skipped: 0 }
+        },
+        Err(e) => {
+            println!("Directory scan task panicked: {}", e);
+            Message::DirectoryAdded { files: Vec::new(), skipped: 0 }
         }
     }
 }
 
+// Renders a path as individual character widgets so matched positions can be
+// colored, since the text widget has no per-span styling of its own.
+fn render_highlighted_path<'a>(path_str: &str, positions: &[usize]) -> Element<'a, Message> {
+    let highlighted: HashSet<usize> = positions.iter().copied().collect();
+
+    let mut rendered = row![].spacing(0);
+    for (i, ch) in path_str.chars().enumerate() {
+        let glyph = if highlighted.contains(&i) {
+            text(ch.to_string()).size(14).style(iced::Color::from_rgb(0.9, 0.5, 0.0))
+        } else {
+            text(ch.to_string()).size(14)
+        };
+        rendered = rendered.push(glyph);
+    }
+
+    rendered.into()
+}
+
+// Watches a top-level folder for filesystem changes and reports them as
+// `Message::FsEvent`, one per affected path. Events for the same path
+// arriving within `FS_WATCH_DEBOUNCE` are coalesced to the most recent kind,
+// so a burst like `cargo build` collapses into a handful of refreshes
+// instead of spamming `update()`.
+fn watch_folder(path: PathBuf) -> Subscription<Message> {
+    iced::subscription::channel(path.clone(), 100, move |mut output| {
+        let path = path.clone();
+        async move {
+            let (raw_tx, mut raw_rx) = tokio::sync::mpsc::unbounded_channel();
+
+            let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    let _ = raw_tx.send(event);
+                }
+            }) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    println!("Failed to create filesystem watcher for {}: {}", path.display(), e);
+                    std::future::pending::<()>().await;
+                    unreachable!()
+                }
+            };
+
+            if let Err(e) = watcher.watch(&path, RecursiveMode::Recursive) {
+                println!("Failed to watch {}: {}", path.display(), e);
+                std::future::pending::<()>().await;
+                unreachable!()
+            }
+
+            let mut pending: std::collections::HashMap<PathBuf, notify::EventKind> = std::collections::HashMap::new();
+
+            loop {
+                let Some(event) = raw_rx.recv().await else {
+                    // The watcher's callback channel closed; there's nothing
+                    // left to report, but the worker future must never
+                    // resolve, so park here instead of returning.
+                    std::future::pending::<()>().await;
+                    unreachable!()
+                };
+                for changed_path in &event.paths {
+                    pending.insert(changed_path.clone(), event.kind.clone());
+                }
+
+                let deadline = tokio::time::sleep(FS_WATCH_DEBOUNCE);
+                tokio::pin!(deadline);
+                loop {
+                    tokio::select! {
+                        _ = &mut deadline => break,
+                        next = raw_rx.recv() => {
+                            match next {
+                                Some(event) => {
+                                    for changed_path in &event.paths {
+                                        pending.insert(changed_path.clone(), event.kind.clone());
+                                    }
+                                },
+                                None => break,
+                            }
+                        }
+                    }
+                }
+
+                for (changed_path, kind) in pending.drain() {
+                    if output.send(Message::FsEvent(changed_path, kind)).await.is_err() {
+                        std::future::pending::<()>().await;
+                        unreachable!()
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn build_prompt_task(builder: PromptBuilder) -> Message {
+    match builder.build_prompt().await {
+        Ok(prompt) => Message::PromptBuilt(prompt),
+        Err(e) => {
+            // In a real implementation, we might want to show this error in the UI
+            println!("Error building prompt: {}", e);
+            Message::PromptBuilt(String::new())
+        }
+    }
+}
+
+// Exporting to a text file is a separate action from `BuildPrompt` (which
+// only previews the assembled prompt): it builds the prompt the same way,
+// then writes it to wherever the user picked in the save dialog.
+async fn export_prompt_task(builder: PromptBuilder, path: PathBuf) -> Message {
+    let prompt = match builder.build_prompt().await {
+        Ok(prompt) => prompt,
+        Err(e) => return Message::PromptExported(Err(format!("Failed to build prompt: {}", e))),
+    };
+
+    match tokio::fs::write(&path, prompt).await {
+        Ok(()) => Message::PromptExported(Ok(path)),
+        Err(e) => Message::PromptExported(Err(format!("Failed to write {}: {}", path.display(), e))),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FilteredItem {
+    path: PathBuf,
+    display: String,
+    score: i64,
+    positions: Vec<usize>,
+}
+
 pub struct FileManager {
     folders: Vec<Folder>,
     prompt_builder: PromptBuilder,
     show_prompt_panel: bool,
+    prompt_store: PromptStore,
+    saved_prompts: Vec<SavedPrompt>,
+    show_library_panel: bool,
+    new_prompt_title: String,
+    filter_query: String,
+    filtered_matches: Vec<FilteredItem>,
+    exclude_patterns_input: String,
+    directory_add_status: Option<String>,
+}
+
+impl FileManager {
+    // Re-score every loaded file against the current filter query. Only
+    // files are matched (not directories), since matches feed directly into
+    // `PromptBuilder::add_file`. Only folders/subdirectories already loaded
+    // into the tree are searched - collapsed, unexpanded subdirectories
+    // haven't been scanned yet.
+    fn refresh_filtered_matches(&mut self) {
+        if self.filter_query.is_empty() {
+            self.filtered_matches.clear();
+            return;
+        }
+
+        let mut matches: Vec<FilteredItem> = self.folders.iter()
+            .flat_map(|folder| folder.items.iter())
+            .filter(|item| item.is_file)
+            .filter_map(|item| {
+                let display = item.path.to_string_lossy().to_string();
+                let FuzzyMatch { score, positions } = fuzzy_match(&self.filter_query, &display)?;
+                Some(FilteredItem { path: item.path.clone(), display, score, positions })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.score.cmp(&a.score));
+        self.filtered_matches = matches;
+    }
 }
 
 impl Application for FileManager {
@@ -57,10 +317,22 @@ impl Application for FileManager {
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Self::Message>) {
+        let prompt_store = PromptStore::open(&PathBuf::from(".prompt_store"))
+            .expect("failed to open prompt store");
+        let saved_prompts = prompt_store.list().unwrap_or_default();
+
         (Self {
             folders: Vec::new(),
             prompt_builder: PromptBuilder::new(),
             show_prompt_panel: false,
+            prompt_store,
+            saved_prompts,
+            show_library_panel: false,
+            new_prompt_title: String::new(),
+            filter_query: String::new(),
+            filtered_matches: Vec::new(),
+            exclude_patterns_input: String::new(),
+            directory_add_status: None,
         }, Command::none())
     }
 
@@ -68,6 +340,10 @@ impl Application for FileManager {
         String::from("Hyprland File Manager")
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::batch(self.folders.iter().map(|folder| watch_folder(folder.path.clone())))
+    }
+
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
             Message::AddFolderPressed => {
@@ -83,26 +359,69 @@ impl Application for FileManager {
                 )
             },
             Message::FolderSelected(Some(path)) => {
-                // Read folder contents
-                if let Ok(entries) = std::fs::read_dir(&path) {
-                    let items: Vec<FileSystemItem> = entries
-                        .filter_map(|entry| {
-                            if let Ok(entry) = entry {
-                                Some(FileSystemItem::new(entry.path()))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-                    
-                    self.folders.push(Folder {
-                        path: path.clone(),
-                        items,
-                    });
+                // Show the folder immediately in a loading state; its
+                // top-level entries are read on a blocking task.
+                self.folders.push(Folder {
+                    path: path.clone(),
+                    items: Vec::new(),
+                    loading: true,
+                });
+                Command::perform(load_folder_contents(path), |message| message)
+            },
+            Message::FolderSelected(None) => {
+                Command::none()
+            },
+            Message::FolderLoaded(path, items) => {
+                if let Some(folder) = self.folders.iter_mut().find(|f| f.path == path) {
+                    folder.items = items;
+                    folder.loading = false;
                 }
+                self.refresh_filtered_matches();
                 Command::none()
             },
-            Message::FolderSelected(None) => {
+            Message::ToggleExpand(path) => {
+                let mut command = Command::none();
+
+                for folder in &mut self.folders {
+                    if let Some(index) = folder.items.iter().position(|item| item.path == path) {
+                        let item = &folder.items[index];
+                        if item.is_file {
+                            break;
+                        }
+
+                        if item.expanded {
+                            folder.items[index].expanded = false;
+                        } else if item.children_loaded {
+                            folder.items[index].expanded = true;
+                        } else if !item.loading {
+                            // Without this guard, double-toggling before
+                            // `ChildrenLoaded` lands would fire a second
+                            // `load_children` for the same path and splice
+                            // its children in twice.
+                            let depth = item.depth;
+                            folder.items[index].loading = true;
+                            command = Command::perform(load_children(path.clone(), depth), |message| message);
+                        }
+                        break;
+                    }
+                }
+
+                command
+            },
+            Message::ChildrenLoaded(path, children) => {
+                for folder in &mut self.folders {
+                    if let Some(index) = folder.items.iter().position(|item| item.path == path) {
+                        let already_loaded = folder.items[index].children_loaded;
+                        folder.items[index].expanded = true;
+                        folder.items[index].loading = false;
+                        if !already_loaded {
+                            folder.items[index].children_loaded = true;
+                            folder.items.splice(index + 1..index + 1, children);
+                        }
+                        break;
+                    }
+                }
+                self.refresh_filtered_matches();
                 Command::none()
             },
             Message::ToggleItemSelected(path, selected) => {
@@ -115,20 +434,17 @@ impl Application for FileManager {
                         }
                     }
                 }
-                
+
                 // Update prompt builder state
                 if selected {
                     if path.is_file() {
                         let _ = self.prompt_builder.add_file(path);
+                        Command::none()
                     } else if path.is_dir() {
-                        match self.prompt_builder.add_directory(path) {
-                            Ok(_count) => {
-                                // Could show a toast/notification: "Added {count} files"
-                            },
-                            Err(_e) => {
-                                // Handle error - could show error message
-                            }
-                        }
+                        let excludes = self.prompt_builder.excludes();
+                        Command::perform(scan_directory_task(path, excludes), |message| message)
+                    } else {
+                        Command::none()
                     }
                 } else {
                     if path.is_file() {
@@ -136,22 +452,19 @@ impl Application for FileManager {
                     } else if path.is_dir() {
                         self.prompt_builder.remove_directory(&path);
                     }
+                    Command::none()
                 }
-                
+            },
+            Message::DirectoryAdded { files, skipped } => {
+                let added = self.prompt_builder.apply_scanned_files(files);
+                self.directory_add_status = Some(format!("Added {} files, skipped {}", added, skipped));
                 Command::none()
             },
             Message::BuildPrompt => {
-                match self.prompt_builder.build_prompt() {
-                    Ok(prompt) => Command::perform(
-                        async move { Message::PromptBuilt(prompt) },
-                        |message| message
-                    ),
-                    Err(e) => {
-                        // In a real implementation, we might want to show this error in the UI
-                        println!("Error building prompt: {}", e);
-                        Command::none()
-                    }
-                }
+                Command::perform(
+                    build_prompt_task(self.prompt_builder.clone()),
+                    |message| message
+                )
             },
             Message::PromptBuilt(prompt) => {
                 // In a real implementation, we would display this prompt in the UI
@@ -162,6 +475,162 @@ impl Application for FileManager {
                 self.show_prompt_panel = !self.show_prompt_panel;
                 Command::none()
             },
+            Message::ToggleLibraryPanel => {
+                self.show_library_panel = !self.show_library_panel;
+                if self.show_library_panel {
+                    self.saved_prompts = self.prompt_store.list().unwrap_or_default();
+                }
+                Command::none()
+            },
+            Message::PromptTitleChanged(title) => {
+                self.new_prompt_title = title;
+                Command::none()
+            },
+            Message::SavePrompt(title) => {
+                if !title.trim().is_empty() {
+                    let id = prompt_store::generate_prompt_id(&title);
+                    match self.prompt_store.save(&id, &title, Vec::new(), self.prompt_builder.get_files()) {
+                        Ok(()) => {
+                            self.saved_prompts = self.prompt_store.list().unwrap_or_default();
+                            self.new_prompt_title.clear();
+                        },
+                        Err(e) => {
+                            println!("Error saving prompt: {}", e);
+                        }
+                    }
+                }
+                Command::none()
+            },
+            Message::LoadPrompt(id) => {
+                match self.prompt_store.load(&id) {
+                    Ok(saved) => {
+                        self.prompt_builder.load_files(saved.files);
+                    },
+                    Err(e) => {
+                        println!("Error loading prompt: {}", e);
+                    }
+                }
+                Command::none()
+            },
+            Message::FilterChanged(query) => {
+                self.filter_query = query;
+                self.refresh_filtered_matches();
+                Command::none()
+            },
+            Message::SelectAllMatches => {
+                let matched_paths: HashSet<PathBuf> = self.filtered_matches.iter()
+                    .map(|m| m.path.clone())
+                    .collect();
+
+                for folder in &mut self.folders {
+                    for item in &mut folder.items {
+                        if matched_paths.contains(&item.path) {
+                            item.selected = true;
+                        }
+                    }
+                }
+
+                for path in matched_paths {
+                    let _ = self.prompt_builder.add_file(path);
+                }
+
+                Command::none()
+            },
+            Message::FsEvent(path, _kind) => {
+                // The cached text/binary classification (if any) may now be
+                // wrong regardless of which branch below applies - the file
+                // could have been rewritten, or removed and replaced by a
+                // directory - so always drop it and let the next lookup
+                // re-read from disk.
+                self.prompt_builder.invalidate_kind_cache(&path);
+
+                // Reconcile against the filesystem directly rather than the
+                // event kind, since renames/atomic saves surface as
+                // Remove+Create pairs that are easier to tell apart by
+                // checking whether the path still exists than by trusting
+                // platform-specific event kinds.
+                if path.exists() {
+                    for folder in &mut self.folders {
+                        if path == folder.path || !path.starts_with(&folder.path) {
+                            continue;
+                        }
+                        if folder.items.iter().any(|item| item.path == path) {
+                            continue;
+                        }
+
+                        let parent = path.parent();
+                        if parent == Some(folder.path.as_path()) {
+                            let new_item = FileSystemItem::new(path.clone(), 0);
+                            let insert_at = folder.items.iter()
+                                .position(|item| item.depth == 0 && item.name > new_item.name)
+                                .unwrap_or(folder.items.len());
+                            folder.items.insert(insert_at, new_item);
+                        } else if let Some(parent_path) = parent {
+                            if let Some(parent_index) = folder.items.iter()
+                                .position(|item| item.path == parent_path && item.children_loaded)
+                            {
+                                let depth = folder.items[parent_index].depth + 1;
+                                folder.items.insert(parent_index + 1, FileSystemItem::new(path.clone(), depth));
+                            }
+                        }
+                        break;
+                    }
+                } else {
+                    // Drop the item (and any descendants) from the tree, but
+                    // leave any `FileReference` the prompt builder holds for
+                    // it alone - `unreadable_files_count` already reports it
+                    // as unreadable dynamically, and a transient removal
+                    // (e.g. an editor's atomic save) shouldn't silently drop
+                    // a file the user explicitly added.
+                    for folder in &mut self.folders {
+                        folder.items.retain(|item| item.path != path && !item.path.starts_with(&path));
+                    }
+                }
+
+                self.refresh_filtered_matches();
+                Command::none()
+            },
+            Message::ExcludePatternsChanged(patterns) => {
+                self.exclude_patterns_input = patterns;
+                Command::none()
+            },
+            Message::ApplyExcludePatterns => {
+                let globs: Vec<String> = self.exclude_patterns_input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                self.prompt_builder.set_excludes(HashSet::new(), globs);
+                Command::none()
+            },
+            Message::ExportPromptPressed => {
+                Command::perform(
+                    async {
+                        let file = rfd::AsyncFileDialog::new()
+                            .set_file_name("prompt.txt")
+                            .save_file()
+                            .await
+                            .map(|handle| handle.path().to_path_buf());
+                        Message::ExportPathSelected(file)
+                    },
+                    |message| message
+                )
+            },
+            Message::ExportPathSelected(Some(path)) => {
+                Command::perform(export_prompt_task(self.prompt_builder.clone(), path), |message| message)
+            },
+            Message::ExportPathSelected(None) => {
+                Command::none()
+            },
+            Message::PromptExported(Ok(path)) => {
+                println!("Exported prompt to {}", path.display());
+                Command::none()
+            },
+            Message::PromptExported(Err(e)) => {
+                println!("Error exporting prompt: {}", e);
+                Command::none()
+            },
         }
     }
 
@@ -185,14 +654,85 @@ impl Application for FileManager {
             .on_press(Message::TogglePromptPanel)
             .padding(10);
 
+        // Add a button to toggle the saved prompt library panel
+        let toggle_library_panel_button = button(text(if self.show_library_panel { "Hide Library" } else { "Show Library" }))
+            .on_press(Message::ToggleLibraryPanel)
+            .padding(10);
+
+        let filter_input = text_input("Fuzzy filter files...", &self.filter_query)
+            .on_input(Message::FilterChanged)
+            .padding(10)
+            .width(iced::Length::Fixed(250.0));
+
+        let exclude_patterns_input = text_input("Exclude patterns (comma separated)...", &self.exclude_patterns_input)
+            .on_input(Message::ExcludePatternsChanged)
+            .on_submit(Message::ApplyExcludePatterns)
+            .padding(10)
+            .width(iced::Length::Fixed(250.0));
+
+        let apply_excludes_button = button(text("Apply Excludes"))
+            .on_press(Message::ApplyExcludePatterns)
+            .padding(10);
+
         let mut content = column![
             row![
                 add_folder_button,
                 build_prompt_button,
-                toggle_prompt_panel_button
+                toggle_prompt_panel_button,
+                toggle_library_panel_button,
+                filter_input
+            ].spacing(10),
+            row![
+                exclude_patterns_input,
+                apply_excludes_button
             ].spacing(10)
         ].spacing(20);
 
+        if let Some(status) = &self.directory_add_status {
+            content = content.push(
+                text(status).size(12).style(iced::Color::from_rgb(0.3, 0.3, 0.3))
+            );
+        }
+
+        if !self.filter_query.is_empty() {
+            let matches_header = text(format!("{} matches", self.filtered_matches.len()))
+                .size(16)
+                .style(iced::Color::from_rgb(0.8, 0.4, 0.0));
+
+            let mut matches_content = column![];
+            for filtered in &self.filtered_matches {
+                matches_content = matches_content.push(render_highlighted_path(&filtered.display, &filtered.positions));
+            }
+
+            let select_all_button = if self.filtered_matches.is_empty() {
+                button(text("Select all matches")).padding(5)
+            } else {
+                button(text(format!("Select all {} matches", self.filtered_matches.len())))
+                    .on_press(Message::SelectAllMatches)
+                    .padding(5)
+            };
+
+            let filter_panel = column![
+                row![matches_header, select_all_button].spacing(10).align_items(iced::Alignment::Center),
+                matches_content
+            ]
+            .spacing(10)
+            .padding(10);
+
+            content = content.push(container(filter_panel)
+                .style(|_theme: &Theme| {
+                    container::Appearance {
+                        border: iced::Border {
+                            color: iced::Color::from_rgb(0.8, 0.4, 0.0),
+                            width: 1.0,
+                            radius: 5.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                })
+            );
+        }
+
         // Display folders and their contents
         for folder in &self.folders {
             let folder_name = folder.path
@@ -200,25 +740,65 @@ impl Application for FileManager {
                 .map(|name| name.to_string_lossy().to_string())
                 .unwrap_or_else(|| "/".to_string());
                 
-            let folder_header = text(format!("Folder: {}", folder_name))
+            let folder_header = text(if folder.loading {
+                format!("Folder: {} (loading...)", folder_name)
+            } else {
+                format!("Folder: {}", folder_name)
+            })
                 .size(18)
                 .style(iced::Color::from_rgb(0.0, 0.5, 1.0));
-                
+
             let mut folder_content = column![];
-            
+            let mut hide_below_depth: Option<usize> = None;
+
             for item in &folder.items {
-                let icon = if item.is_file { "📄" } else { "📁" };
-                let item_text = format!("{} {}", icon, item.name);
-                
+                if let Some(depth) = hide_below_depth {
+                    if item.depth > depth {
+                        continue;
+                    }
+                    hide_below_depth = None;
+                }
+
+                let icon = if item.is_file {
+                    "📄"
+                } else if item.expanded {
+                    "📂"
+                } else {
+                    "📁"
+                };
+                let item_text = if item.loading {
+                    format!("{} {} (loading...)", icon, item.name)
+                } else {
+                    format!("{} {}", icon, item.name)
+                };
+
+                let expand_toggle: Element<Message> = if item.is_file {
+                    text("  ").size(14).into()
+                } else if item.loading {
+                    text("…").size(12).into()
+                } else {
+                    let arrow = if item.expanded { "▼" } else { "▶" };
+                    button(text(arrow).size(12))
+                        .on_press(Message::ToggleExpand(item.path.clone()))
+                        .padding(2)
+                        .into()
+                };
+
                 let item_row = row![
+                    container(text("")).width(iced::Length::Fixed((item.depth * 20) as f32)),
+                    expand_toggle,
                     checkbox("", item.selected)
                         .on_toggle(move |checked| Message::ToggleItemSelected(item.path.clone(), checked)),
                     text(item_text).size(14)
                 ]
                 .spacing(10)
                 .align_items(iced::Alignment::Center);
-                
+
                 folder_content = folder_content.push(item_row);
+
+                if !item.is_file && !item.expanded {
+                    hide_below_depth = Some(item.depth);
+                }
             }
             
             let folder_section = column![
@@ -239,37 +819,56 @@ impl Application for FileManager {
             let file_info = self.prompt_builder.get_file_info();
             let readable_count = self.prompt_builder.readable_files_count();
             let unreadable_count = self.prompt_builder.unreadable_files_count();
-            
+            let binary_count = self.prompt_builder.binary_files_count();
+
             let stats_text = format!(
-                "Files: {} total ({} readable, {} unreadable)",
+                "Files: {} total ({} readable incl. {} binary, {} unreadable)",
                 file_info.len(),
                 readable_count,
+                binary_count,
                 unreadable_count
             );
-            
+
             let stats_row = text(stats_text).size(12).style(iced::Color::from_rgb(0.3, 0.3, 0.3));
-            
+
             let mut prompt_files_content = column![];
-            
-            for (display_name, size) in file_info {
+
+            for (display_name, size, kind) in file_info {
+                let icon = if kind == prompt_builder::FileKind::Binary { "🗄️" } else { "📄" };
                 let file_row = row![
-                    text(format!("📄 {}", display_name)).size(14),
+                    text(format!("{} {}", icon, display_name)).size(14),
                     text(size).size(12).style(iced::Color::from_rgb(0.5, 0.5, 0.5))
                 ]
                 .spacing(10)
                 .align_items(iced::Alignment::Center);
-                
+
                 prompt_files_content = prompt_files_content.push(file_row);
             }
             
+            let save_prompt_row = row![
+                text_input("Name this prompt...", &self.new_prompt_title)
+                    .on_input(Message::PromptTitleChanged)
+                    .on_submit(Message::SavePrompt(self.new_prompt_title.clone()))
+                    .padding(5),
+                button(text("Save Prompt"))
+                    .on_press(Message::SavePrompt(self.new_prompt_title.clone()))
+                    .padding(5),
+                button(text("Export to File"))
+                    .on_press(Message::ExportPromptPressed)
+                    .padding(5),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center);
+
             let prompt_panel = column![
                 prompt_panel_header,
                 stats_row,
-                prompt_files_content
+                prompt_files_content,
+                save_prompt_row
             ]
             .spacing(10)
             .padding(10);
-            
+
             content = content.push(container(prompt_panel)
                 .style(|_theme: &Theme| {
                     container::Appearance {
@@ -284,6 +883,50 @@ impl Application for FileManager {
             );
         }
 
+        // Display the saved prompt library if toggled on
+        if self.show_library_panel {
+            let library_header = text("Saved Prompts")
+                .size(18)
+                .style(iced::Color::from_rgb(0.6, 0.3, 0.9));
+
+            let mut library_content = column![];
+
+            for saved in &self.saved_prompts {
+                let id = saved.id.clone();
+                let label = format!("{} ({} files)", saved.metadata.title, saved.files.len());
+                let saved_row = row![
+                    text(label).size(14),
+                    button(text("Load"))
+                        .on_press(Message::LoadPrompt(id))
+                        .padding(5),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center);
+
+                library_content = library_content.push(saved_row);
+            }
+
+            let library_panel = column![
+                library_header,
+                library_content
+            ]
+            .spacing(10)
+            .padding(10);
+
+            content = content.push(container(library_panel)
+                .style(|_theme: &Theme| {
+                    container::Appearance {
+                        border: iced::Border {
+                            color: iced::Color::from_rgb(0.6, 0.3, 0.9),
+                            width: 1.0,
+                            radius: 5.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                })
+            );
+        }
+
         container(scrollable(content))
             .padding(20)
             .width(iced::Length::Fill)
@@ -1,7 +1,141 @@
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::io;
 
+use futures::stream::{self, StreamExt};
+use tokio::io::AsyncReadExt;
+
+mod excludes;
+pub use excludes::ExcludedItems;
+
+// How many files `build_prompt` will read at once.
+const BUILD_PROMPT_CONCURRENCY: usize = 8;
+
+// How many leading bytes of a file are sampled when classifying text vs binary.
+const CLASSIFY_SAMPLE_SIZE: usize = 8 * 1024;
+
+const BINARY_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "ico", "webp", "pdf", "zip", "gz", "tar", "7z", "rar",
+    "exe", "dll", "so", "dylib", "bin", "class", "o", "a", "wasm", "mp3", "mp4", "mov", "avi",
+    "mkv", "woff", "woff2", "ttf", "otf", "sqlite", "db",
+];
+
+// Whether a file is prompt-safe plaintext or should be shown as a placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Text,
+    Binary,
+}
+
+fn is_binary_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| BINARY_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+}
+
+// A NUL byte or a high ratio of non-text control bytes in the leading sample
+// means the rest of the file is almost certainly not meant to be read as text.
+fn classify_sample(sample: &[u8]) -> FileKind {
+    if sample.contains(&0) {
+        return FileKind::Binary;
+    }
+
+    let control_bytes = sample
+        .iter()
+        .filter(|&&b| b < 0x09 || (b > 0x0d && b < 0x20))
+        .count();
+
+    if !sample.is_empty() && (control_bytes as f64 / sample.len() as f64) > 0.3 {
+        return FileKind::Binary;
+    }
+
+    FileKind::Text
+}
+
+fn guess_mime_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+        Some(ext) => match ext.as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "bmp" => "image/bmp",
+            "ico" => "image/x-icon",
+            "webp" => "image/webp",
+            "pdf" => "application/pdf",
+            "zip" => "application/zip",
+            "gz" => "application/gzip",
+            "tar" => "application/x-tar",
+            "wasm" => "application/wasm",
+            "mp3" => "audio/mpeg",
+            "mp4" => "video/mp4",
+            "mov" => "video/quicktime",
+            "woff" => "font/woff",
+            "woff2" => "font/woff2",
+            "ttf" => "font/ttf",
+            "otf" => "font/otf",
+            _ => "application/octet-stream",
+        },
+        None => "application/octet-stream",
+    }
+}
+
+fn human_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{} KB", bytes / KB)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+// Synchronous classification for call sites outside the async build path
+// (e.g. rendering file info in the UI).
+fn classify_file_sync(path: &Path) -> FileKind {
+    if is_binary_extension(path) {
+        return FileKind::Binary;
+    }
+
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return FileKind::Text,
+    };
+
+    let mut buffer = vec![0u8; CLASSIFY_SAMPLE_SIZE];
+    let read = match file.read(&mut buffer) {
+        Ok(n) => n,
+        Err(_) => return FileKind::Text,
+    };
+
+    classify_sample(&buffer[..read])
+}
+
+// Async classification used while concurrently reading files for the prompt.
+async fn classify_file_async(path: &Path) -> FileKind {
+    if is_binary_extension(path) {
+        return FileKind::Binary;
+    }
+
+    let mut file = match tokio::fs::File::open(path).await {
+        Ok(f) => f,
+        Err(_) => return FileKind::Text,
+    };
+
+    let mut buffer = vec![0u8; CLASSIFY_SAMPLE_SIZE];
+    let read = match file.read(&mut buffer).await {
+        Ok(n) => n,
+        Err(_) => return FileKind::Text,
+    };
+
+    classify_sample(&buffer[..read])
+}
+
 #[derive(Debug, Clone)]
 pub struct FileReference {
     pub path: PathBuf,
@@ -17,6 +151,7 @@ impl FileReference {
             order,
         }
     }
+
 }
 
 #[derive(Debug, Clone, Default)]
@@ -33,14 +168,52 @@ pub struct PromptState {
 #[derive(Debug, Clone)]
 pub struct PromptBuilder {
     state: PromptState,
+    excludes: ExcludedItems,
+    // Classifying a file means opening and sampling it, which is too slow to
+    // redo synchronously on every `view()` call; cache by path and drop an
+    // entry via `invalidate_kind_cache` once the file changes on disk. A
+    // `RefCell` lets the cache fill in from `&self` lookups like
+    // `get_file_info`, which iced calls on every render.
+    kind_cache: RefCell<HashMap<PathBuf, FileKind>>,
 }
 
 impl PromptBuilder {
     pub fn new() -> Self {
         Self {
             state: PromptState::default(),
+            excludes: ExcludedItems::defaults(),
+            kind_cache: RefCell::new(HashMap::new()),
         }
     }
+
+    // Looks up `path`'s cached text/binary classification, computing and
+    // storing it on first use. Stale entries must be cleared explicitly via
+    // `invalidate_kind_cache`, since nothing here watches the file for us.
+    fn cached_kind(&self, path: &Path) -> FileKind {
+        if let Some(kind) = self.kind_cache.borrow().get(path) {
+            return *kind;
+        }
+
+        let kind = classify_file_sync(path);
+        self.kind_cache.borrow_mut().insert(path.to_path_buf(), kind);
+        kind
+    }
+
+    // Forget `path`'s cached classification so the next lookup re-reads the
+    // file from disk. Call this when a watched path is created, modified, or
+    // removed (e.g. on `Message::FsEvent`).
+    pub fn invalidate_kind_cache(&self, path: &Path) {
+        self.kind_cache.borrow_mut().remove(path);
+    }
+
+    // Layers user-supplied excludes on top of the built-in defaults, rather
+    // than replacing them outright, so a settings panel can't accidentally
+    // re-enable scanning `target`/`.git`/`node_modules`.
+    pub fn set_excludes(&mut self, names: HashSet<String>, globs: Vec<String>) {
+        let mut excludes = ExcludedItems::defaults();
+        excludes.merge(ExcludedItems::new(names, globs));
+        self.excludes = excludes;
+    }
     
     pub fn add_file(&mut self, path: PathBuf) -> Result<(), String> {
         // Check if file already exists in prompt
@@ -67,49 +240,95 @@ impl PromptBuilder {
         Ok(())
     }
     
-    pub fn add_directory(&mut self, dir_path: PathBuf) -> Result<usize, String> {
+    pub fn excludes(&self) -> ExcludedItems {
+        self.excludes.clone()
+    }
+
+    // Pure directory walk with no access to builder state, so it can run on
+    // a blocking task pool instead of the UI thread. Returns the files found
+    // (path, display name) plus a skipped-count; call `apply_scanned_files`
+    // with the result once it lands back on the update loop.
+    pub fn scan_directory(dir_path: &Path, excludes: &ExcludedItems) -> Result<(Vec<(PathBuf, String)>, usize), String> {
         if !dir_path.is_dir() {
             return Err("Path is not a directory".to_string());
         }
-        
-        let mut added_count = 0;
-        self.add_directory_recursive(&dir_path, &mut added_count)?;
-        self.state.next_order += added_count;
-        
-        Ok(added_count)
+
+        let mut found = Vec::new();
+        let mut skipped = 0;
+        Self::scan_directory_recursive(dir_path, dir_path, excludes, &mut found, &mut skipped)?;
+
+        Ok((found, skipped))
     }
-    
-    fn add_directory_recursive(&mut self, dir: &std::path::Path, count: &mut usize) -> Result<(), String> {
+
+    // Walks `dir`, merging in its own `.gitignore` (if any) on top of what
+    // was inherited from ancestors before filtering its entries, so nested
+    // `.gitignore`s are honored - not just the one at the scan root - and a
+    // subtree's patterns never leak into sibling directories.
+    fn scan_directory_recursive(
+        root: &Path,
+        dir: &Path,
+        inherited_excludes: &ExcludedItems,
+        found: &mut Vec<(PathBuf, String)>,
+        skipped: &mut usize,
+    ) -> Result<(), String> {
+        let mut excludes = inherited_excludes.clone();
+        let gitignore_path = dir.join(".gitignore");
+        if gitignore_path.is_file() {
+            excludes.merge(ExcludedItems::from_gitignore(&gitignore_path));
+        }
+        let excludes = &excludes;
+
         let entries = fs::read_dir(dir)
             .map_err(|e| format!("Failed to read directory: {}", e))?;
-        
+
         for entry in entries {
             let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
             let path = entry.path();
-            
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+
             if path.is_file() {
-                // Skip if already added
-                if !self.state.file_contexts.iter().any(|f| f.path == path) {
-                    let display_name = path.strip_prefix(dir)
-                        .unwrap_or(&path)
-                        .to_string_lossy()
-                        .to_string();
-                    
-                    self.state.file_contexts.push(FileReference::new(
-                        path.clone(),
-                        display_name,
-                        self.state.next_order + *count,
-                    ));
-                    *count += 1;
+                if excludes.is_path_excluded(&path, root, false) {
+                    *skipped += 1;
+                    continue;
                 }
+
+                found.push((path, relative));
             } else if path.is_dir() {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if excludes.is_name_excluded(&name) || excludes.is_path_excluded(&path, root, true) {
+                    *skipped += 1;
+                    continue;
+                }
+
                 // Recursively process subdirectories
-                self.add_directory_recursive(&path, count)?;
+                Self::scan_directory_recursive(root, &path, excludes, found, skipped)?;
             }
         }
-        
+
         Ok(())
     }
+
+    // Merge files found by `scan_directory` into the builder, skipping any
+    // already present. Returns how many were actually added.
+    pub fn apply_scanned_files(&mut self, files: Vec<(PathBuf, String)>) -> usize {
+        let mut added = 0;
+
+        for (path, display_name) in files {
+            if self.state.file_contexts.iter().any(|f| f.path == path) {
+                continue;
+            }
+
+            self.state.file_contexts.push(FileReference::new(
+                path,
+                display_name,
+                self.state.next_order,
+            ));
+            self.state.next_order += 1;
+            added += 1;
+        }
+
+        added
+    }
     
     pub fn remove_file(&mut self, path: &PathBuf) {
         self.state.file_contexts.retain(|f| &f.path != path);
@@ -123,6 +342,12 @@ impl PromptBuilder {
         self.state.file_contexts.clear();
         self.state.next_order = 0;
     }
+
+    // Replace the current file set with one loaded from a saved prompt.
+    pub fn load_files(&mut self, files: Vec<FileReference>) {
+        self.state.next_order = files.iter().map(|f| f.order + 1).max().unwrap_or(0);
+        self.state.file_contexts = files;
+    }
     
     pub fn get_files(&self) -> &Vec<FileReference> {
         &self.state.file_contexts
@@ -132,36 +357,60 @@ impl PromptBuilder {
         self.state.file_contexts.len()
     }
     
-    // Build the actual prompt by reading file contents
-    pub fn build_prompt(&self) -> Result<String, io::Error> {
-        let mut prompt = String::new();
-        
-        for file_ref in &self.state.file_contexts {
-            prompt.push_str(&format!("=== File: {} ===\n", file_ref.display_name));
-            
-            match fs::read_to_string(&file_ref.path) {
-                Ok(content) => {
-                    prompt.push_str(&content);
-                    prompt.push_str("\n\n");
-                },
-                Err(e) => {
-                    prompt.push_str(&format!("Error reading file: {}\n\n", e));
+    // Build the prompt by reading file contents concurrently, bounded so a
+    // large file set doesn't open everything at once. Order is preserved
+    // regardless of which read finishes first.
+    pub async fn build_prompt(&self) -> Result<String, io::Error> {
+        let files = self.state.file_contexts.clone();
+
+        let mut sections: Vec<(usize, String)> = stream::iter(files.into_iter().map(|file_ref| async move {
+            let section = if classify_file_async(&file_ref.path).await == FileKind::Binary {
+                let size = tokio::fs::metadata(&file_ref.path).await.map(|m| m.len()).unwrap_or(0);
+                let mime = guess_mime_type(&file_ref.path);
+                format!(
+                    "=== File: {} (binary, {}, {}) ===\n\n",
+                    file_ref.display_name,
+                    human_size(size),
+                    mime,
+                )
+            } else {
+                let mut section = format!("=== File: {} ===\n", file_ref.display_name);
+                match tokio::fs::read_to_string(&file_ref.path).await {
+                    Ok(content) => {
+                        section.push_str(&content);
+                        section.push_str("\n\n");
+                    },
+                    Err(e) => {
+                        section.push_str(&format!("Error reading file: {}\n\n", e));
+                    }
                 }
-            }
+                section
+            };
+            (file_ref.order, section)
+        }))
+        .buffer_unordered(BUILD_PROMPT_CONCURRENCY)
+        .collect()
+        .await;
+
+        sections.sort_by_key(|(order, _)| *order);
+
+        let mut prompt = String::new();
+        for (_, section) in sections {
+            prompt.push_str(&section);
         }
-        
+
         Ok(prompt)
     }
     
     // Get file information without reading content (lazy loading)
-    pub fn get_file_info(&self) -> Vec<(String, String)> {
+    pub fn get_file_info(&self) -> Vec<(String, String, FileKind)> {
         self.state.file_contexts.iter().map(|file_ref| {
             let metadata = fs::metadata(&file_ref.path);
             let size = match metadata {
                 Ok(meta) => format!("{} bytes", meta.len()),
                 Err(_) => "Unknown size".to_string(),
             };
-            (file_ref.display_name.clone(), size)
+            (file_ref.display_name.clone(), size, self.cached_kind(&file_ref.path))
         }).collect()
     }
     
@@ -183,4 +432,51 @@ impl PromptBuilder {
             !file_ref.path.exists() || !file_ref.path.is_file()
         }).count()
     }
+
+    // Get count of files intentionally skipped as binary, distinct from
+    // files that are missing or otherwise unreadable.
+    pub fn binary_files_count(&self) -> usize {
+        self.state.file_contexts.iter().filter(|file_ref| {
+            file_ref.path.exists() && file_ref.path.is_file() && self.cached_kind(&file_ref.path) == FileKind::Binary
+        }).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_plain_text_as_text() {
+        assert_eq!(classify_sample(b"fn main() {\n    println!(\"hi\");\n}\n"), FileKind::Text);
+    }
+
+    #[test]
+    fn classifies_empty_sample_as_text() {
+        assert_eq!(classify_sample(b""), FileKind::Text);
+    }
+
+    #[test]
+    fn classifies_nul_byte_as_binary() {
+        assert_eq!(classify_sample(b"abc\0def"), FileKind::Binary);
+    }
+
+    #[test]
+    fn classifies_high_control_byte_ratio_as_binary() {
+        let sample: Vec<u8> = (0..32).map(|i| if i % 2 == 0 { 0x01 } else { b'a' }).collect();
+        assert_eq!(classify_sample(&sample), FileKind::Binary);
+    }
+
+    #[test]
+    fn tolerates_occasional_control_bytes() {
+        let mut sample = b"mostly plain text with just one odd byte here: ".to_vec();
+        sample.push(0x01);
+        sample.extend_from_slice(b" and the rest is ordinary prose.");
+        assert_eq!(classify_sample(&sample), FileKind::Text);
+    }
+
+    #[test]
+    fn common_whitespace_control_bytes_do_not_count_as_binary_signal() {
+        assert_eq!(classify_sample(b"line one\r\nline two\tindented\n"), FileKind::Text);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// A single compiled exclude pattern, supporting the subset of .gitignore
+// syntax we need: a leading `/` anchors the pattern to the scanned root,
+// and a trailing `/` restricts it to directories.
+#[derive(Debug, Clone)]
+struct CompiledGlob {
+    pattern: String,
+    anchored: bool,
+    dir_only: bool,
+}
+
+impl CompiledGlob {
+    fn compile(raw: &str) -> Self {
+        let mut pattern = raw.trim();
+
+        let anchored = pattern.starts_with('/');
+        if anchored {
+            pattern = &pattern[1..];
+        }
+
+        let dir_only = pattern.ends_with('/');
+        if dir_only {
+            pattern = &pattern[..pattern.len() - 1];
+        }
+
+        Self {
+            pattern: pattern.to_string(),
+            anchored,
+            dir_only,
+        }
+    }
+
+    // `relative_path` is slash-separated and relative to the pattern's anchor
+    // directory (see `ExcludedItems::is_path_excluded`).
+    fn matches(&self, relative_path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        if self.anchored {
+            return glob_match(&self.pattern, relative_path);
+        }
+
+        glob_match(&self.pattern, relative_path)
+            || relative_path
+                .rsplit('/')
+                .next()
+                .is_some_and(|name| glob_match(&self.pattern, name))
+    }
+}
+
+// Minimal shell-style glob matcher supporting `*` and `?`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn helper(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => helper(&pattern[1..], text) || (!text.is_empty() && helper(pattern, &text[1..])),
+            (Some(b'?'), Some(_)) => helper(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => helper(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+
+    helper(pattern.as_bytes(), text.as_bytes())
+}
+
+// A compiled glob paired with the directory an anchored (leading `/`)
+// pattern is anchored to. `base_dir: None` means "anchor to whatever root
+// `scan_directory` was called with" - used for the built-in defaults and
+// user-supplied patterns, which aren't tied to any one `.gitignore`.
+#[derive(Debug, Clone)]
+struct AnchoredGlob {
+    base_dir: Option<PathBuf>,
+    glob: CompiledGlob,
+}
+
+// Holds the literal directory names and glob patterns to skip while
+// recursively adding a directory to the prompt.
+#[derive(Debug, Clone, Default)]
+pub struct ExcludedItems {
+    names: HashSet<String>,
+    globs: Vec<AnchoredGlob>,
+}
+
+impl ExcludedItems {
+    pub fn new(names: HashSet<String>, patterns: Vec<String>) -> Self {
+        Self::with_base(names, patterns, None)
+    }
+
+    fn with_base(names: HashSet<String>, patterns: Vec<String>, base_dir: Option<PathBuf>) -> Self {
+        Self {
+            names,
+            globs: patterns.iter()
+                .map(|p| AnchoredGlob { base_dir: base_dir.clone(), glob: CompiledGlob::compile(p) })
+                .collect(),
+        }
+    }
+
+    // Sensible defaults so a fresh `PromptBuilder` doesn't pull in build
+    // artifacts and dependency caches before the user configures excludes.
+    pub fn defaults() -> Self {
+        Self::new(
+            HashSet::from([
+                "target".to_string(),
+                ".git".to_string(),
+                "node_modules".to_string(),
+            ]),
+            vec![
+                "*.lock".to_string(),
+                "*.rlib".to_string(),
+                "*.so".to_string(),
+                "*.o".to_string(),
+            ],
+        )
+    }
+
+    // Parse a `.gitignore` file's patterns, ignoring blank lines and
+    // comments. Anchored patterns (leading `/`) are resolved against the
+    // directory containing this `.gitignore`, not the overall scan root.
+    pub fn from_gitignore(gitignore_path: &Path) -> Self {
+        let patterns = fs::read_to_string(gitignore_path)
+            .map(|contents| {
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let base_dir = gitignore_path.parent().map(Path::to_path_buf);
+        Self::with_base(HashSet::new(), patterns, base_dir)
+    }
+
+    pub fn merge(&mut self, other: ExcludedItems) {
+        self.names.extend(other.names);
+        self.globs.extend(other.globs);
+    }
+
+    pub fn is_name_excluded(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    // `path` is the absolute path of the entry being checked; `scan_root` is
+    // the directory `scan_directory` was called with, used as the anchor
+    // base for patterns that aren't tied to a specific `.gitignore`.
+    pub fn is_path_excluded(&self, path: &Path, scan_root: &Path, is_dir: bool) -> bool {
+        self.globs.iter().any(|ag| {
+            let base = ag.base_dir.as_deref().unwrap_or(scan_root);
+            let relative = path.strip_prefix(base).unwrap_or(path).to_string_lossy().replace('\\', "/");
+            ag.glob.matches(&relative, is_dir)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(!glob_match("*.rs", "main.rs.bak"));
+        assert!(glob_match("fo?.rs", "foo.rs"));
+        assert!(!glob_match("fo?.rs", "fooo.rs"));
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_basename_at_any_depth() {
+        let glob = CompiledGlob::compile("*.lock");
+        assert!(glob.matches("Cargo.lock", false));
+        assert!(glob.matches("sub/dir/Cargo.lock", false));
+    }
+
+    #[test]
+    fn anchored_pattern_only_matches_full_relative_path() {
+        let glob = CompiledGlob::compile("/build");
+        assert!(glob.matches("build", true));
+        assert!(!glob.matches("sub/build", true));
+    }
+
+    #[test]
+    fn dir_only_pattern_does_not_match_files() {
+        let glob = CompiledGlob::compile("logs/");
+        assert!(glob.matches("logs", true));
+        assert!(!glob.matches("logs", false));
+    }
+
+    #[test]
+    fn is_name_excluded_checks_literal_names() {
+        let excludes = ExcludedItems::new(HashSet::from(["target".to_string()]), vec![]);
+        assert!(excludes.is_name_excluded("target"));
+        assert!(!excludes.is_name_excluded("src"));
+    }
+
+    #[test]
+    fn defaults_exclude_globs_ignore_build_artifacts() {
+        let excludes = ExcludedItems::defaults();
+        let root = Path::new("/repo");
+        assert!(excludes.is_path_excluded(&root.join("Cargo.lock"), root, false));
+        assert!(!excludes.is_path_excluded(&root.join("Cargo.toml"), root, false));
+    }
+
+    // Regression test: an anchored pattern from a nested `.gitignore` must
+    // resolve against that `.gitignore`'s own directory, not the overall
+    // scan root - otherwise `/build` in `sub/.gitignore` would need to match
+    // `sub/build` as if it were written `/sub/build`, and never would.
+    #[test]
+    fn anchored_pattern_from_nested_gitignore_resolves_against_its_own_directory() {
+        let root = Path::new("/repo");
+        let nested_excludes = ExcludedItems::with_base(
+            HashSet::new(),
+            vec!["/build".to_string()],
+            Some(root.join("sub")),
+        );
+
+        assert!(nested_excludes.is_path_excluded(&root.join("sub/build"), root, true));
+        assert!(!nested_excludes.is_path_excluded(&root.join("build"), root, true));
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use heed::types::Str;
+use heed::{Database, Env, EnvOpenOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::prompt_builder::FileReference;
+
+const PROMPTS_DB_NAME: &str = "prompts";
+const DEFAULT_MAP_SIZE: usize = 10 * 1024 * 1024;
+
+// Frontmatter metadata stored alongside each saved prompt's file list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMetadata {
+    pub title: String,
+    pub created: u64,
+    pub updated: u64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SavedPrompt {
+    pub id: String,
+    pub metadata: PromptMetadata,
+    pub files: Vec<FileReference>,
+}
+
+// Embedded LMDB store making saved prompt templates authoritative over the
+// file system; `build_prompt()` remains the separate path for exporting to text.
+pub struct PromptStore {
+    env: Env,
+    db: Database<Str, Str>,
+}
+
+impl PromptStore {
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create prompt store directory: {}", e))?;
+
+        let env = unsafe {
+            EnvOpenOptions::new()
+                .map_size(DEFAULT_MAP_SIZE)
+                .open(dir)
+                .map_err(|e| format!("Failed to open prompt store: {}", e))?
+        };
+
+        let mut wtxn = env
+            .write_txn()
+            .map_err(|e| format!("Failed to start prompt store transaction: {}", e))?;
+        let db = env
+            .create_database(&mut wtxn, Some(PROMPTS_DB_NAME))
+            .map_err(|e| format!("Failed to open prompts database: {}", e))?;
+        wtxn.commit()
+            .map_err(|e| format!("Failed to commit prompt store setup: {}", e))?;
+
+        Ok(Self { env, db })
+    }
+
+    // Save or overwrite a prompt. Preserves the original `created` timestamp
+    // when overwriting an existing id.
+    pub fn save(
+        &self,
+        id: &str,
+        title: &str,
+        tags: Vec<String>,
+        files: &[FileReference],
+    ) -> Result<(), String> {
+        let now = unix_now();
+        let created = self.load(id).map(|p| p.metadata.created).unwrap_or(now);
+
+        let metadata = PromptMetadata {
+            title: title.to_string(),
+            created,
+            updated: now,
+            tags,
+        };
+        let document = encode(&metadata, files)?;
+
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .map_err(|e| format!("Failed to start prompt store transaction: {}", e))?;
+        self.db
+            .put(&mut wtxn, id, &document)
+            .map_err(|e| format!("Failed to save prompt: {}", e))?;
+        wtxn.commit()
+            .map_err(|e| format!("Failed to commit saved prompt: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn load(&self, id: &str) -> Result<SavedPrompt, String> {
+        let rtxn = self
+            .env
+            .read_txn()
+            .map_err(|e| format!("Failed to start prompt store transaction: {}", e))?;
+        let document = self
+            .db
+            .get(&rtxn, id)
+            .map_err(|e| format!("Failed to read prompt: {}", e))?
+            .ok_or_else(|| format!("No saved prompt with id {}", id))?;
+
+        decode(id, document)
+    }
+
+    pub fn list(&self) -> Result<Vec<SavedPrompt>, String> {
+        let rtxn = self
+            .env
+            .read_txn()
+            .map_err(|e| format!("Failed to start prompt store transaction: {}", e))?;
+
+        let mut prompts = Vec::new();
+        for entry in self
+            .db
+            .iter(&rtxn)
+            .map_err(|e| format!("Failed to list saved prompts: {}", e))?
+        {
+            let (id, document) = entry.map_err(|e| format!("Failed to read prompt entry: {}", e))?;
+            prompts.push(decode(id, document)?);
+        }
+
+        prompts.sort_by(|a, b| b.metadata.updated.cmp(&a.metadata.updated));
+        Ok(prompts)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .map_err(|e| format!("Failed to start prompt store transaction: {}", e))?;
+        self.db
+            .delete(&mut wtxn, id)
+            .map_err(|e| format!("Failed to delete prompt: {}", e))?;
+        wtxn.commit()
+            .map_err(|e| format!("Failed to commit prompt deletion: {}", e))?;
+
+        Ok(())
+    }
+}
+
+// Generates a human-recognizable id: a slug of the title plus the save time
+// in whole seconds, so saves of the same title made in different seconds get
+// distinct ids; two saves of one title within the same second share an id.
+pub fn generate_prompt_id(title: &str) -> String {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    let slug = if slug.is_empty() { "prompt" } else { slug };
+
+    format!("{}-{}", slug, unix_now())
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn encode(metadata: &PromptMetadata, files: &[FileReference]) -> Result<String, String> {
+    let frontmatter = serde_yaml::to_string(metadata)
+        .map_err(|e| format!("Failed to serialize prompt metadata: {}", e))?;
+
+    let mut document = String::new();
+    document.push_str("---\n");
+    document.push_str(&frontmatter);
+    document.push_str("---\n");
+    for file in files {
+        document.push_str(&format!("{}|{}\n", file.path.display(), file.display_name));
+    }
+
+    Ok(document)
+}
+
+fn decode(id: &str, document: &str) -> Result<SavedPrompt, String> {
+    let mut sections = document.splitn(3, "---\n");
+    sections.next(); // everything before the opening `---`, always empty
+    let frontmatter = sections
+        .next()
+        .ok_or_else(|| "Saved prompt is missing its frontmatter block".to_string())?;
+    let body = sections.next().unwrap_or("");
+
+    let metadata: PromptMetadata = serde_yaml::from_str(frontmatter)
+        .map_err(|e| format!("Failed to parse prompt metadata: {}", e))?;
+
+    let files = body
+        .lines()
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .filter_map(|(order, line)| {
+            let (path, display_name) = line.split_once('|')?;
+            Some(FileReference::new(
+                PathBuf::from(path),
+                display_name.to_string(),
+                order,
+            ))
+        })
+        .collect();
+
+    Ok(SavedPrompt {
+        id: id.to_string(),
+        metadata,
+        files,
+    })
+}